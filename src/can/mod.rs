@@ -1,4 +1,6 @@
 
+use io::{self, BigEndian, ByteOrder, Error};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct BaseID(u16);
 
@@ -215,6 +217,13 @@ impl RemoteFrame {
         }
     }
     
+    pub fn set_data_length(&mut self, length: usize) {
+        match *self {
+            RemoteFrame::BaseRemoteFrame(ref mut f) => f.set_data_length(length),
+            RemoteFrame::ExtendedRemoteFrame(ref mut f) => f.set_data_length(length),
+        }
+    }
+
     pub fn id(&self) -> ID {
         match *self {
             RemoteFrame::BaseRemoteFrame(f) => ID::BaseID(f.id()),
@@ -223,16 +232,332 @@ impl RemoteFrame {
     }
 }
 
+/// The set of payload sizes a CAN FD frame may carry, in bytes.
+const FD_DATA_LENGTHS: [usize; 16] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 12, 16, 20, 24, 32, 48, 64];
+
+/// Maps a requested byte count up to the nearest valid CAN FD payload size and returns its 4-bit
+/// data-length code.
+fn fd_dlc(length: usize) -> u8 {
+    for (dlc, &valid) in FD_DATA_LENGTHS.iter().enumerate() {
+        if length <= valid {
+            return dlc as u8;
+        }
+    }
+    // Larger than the maximum payload clamps to the 64-byte code.
+    15
+}
+
+/// Returns the payload size in bytes described by a 4-bit data-length code.
+fn fd_data_length(dlc: u8) -> usize {
+    FD_DATA_LENGTHS[(dlc & 0xf) as usize]
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FdBaseDataFrame {
+    id: BaseID,
+    dlc: u8,
+    brs: bool,
+    esi: bool,
+    data: [u8; 64],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FdExtendedDataFrame {
+    id: ExtendedID,
+    dlc: u8,
+    brs: bool,
+    esi: bool,
+    data: [u8; 64],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FdDataFrame {
+    /// A CAN FD frame with an 11-bit base identifier
+    FdBaseDataFrame(FdBaseDataFrame),
+
+    /// A CAN FD frame with a 29-bit extended identifier
+    FdExtendedDataFrame(FdExtendedDataFrame),
+}
+
+impl FdBaseDataFrame {
+    pub fn new(id: BaseID) -> Self {
+        Self{id: id, dlc: 0, brs: false, esi: false, data: [0; 64]}
+    }
+
+    pub fn set_data_length(&mut self, length: usize) {
+        self.dlc = fd_dlc(length);
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data[0..fd_data_length(self.dlc)]
+    }
+
+    pub fn data_as_mut(&mut self) -> &mut[u8] {
+        let length = fd_data_length(self.dlc);
+        &mut self.data[0..length]
+    }
+
+    pub fn brs(&self) -> bool {
+        self.brs
+    }
+
+    pub fn set_brs(&mut self, brs: bool) {
+        self.brs = brs;
+    }
+
+    pub fn esi(&self) -> bool {
+        self.esi
+    }
+
+    pub fn set_esi(&mut self, esi: bool) {
+        self.esi = esi;
+    }
+
+    pub fn id(&self) -> BaseID {
+        self.id
+    }
+}
+
+impl FdExtendedDataFrame {
+    pub fn new(id: ExtendedID) -> Self {
+        Self{id: id, dlc: 0, brs: false, esi: false, data: [0; 64]}
+    }
+
+    pub fn set_data_length(&mut self, length: usize) {
+        self.dlc = fd_dlc(length);
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data[0..fd_data_length(self.dlc)]
+    }
+
+    pub fn data_as_mut(&mut self) -> &mut[u8] {
+        let length = fd_data_length(self.dlc);
+        &mut self.data[0..length]
+    }
+
+    pub fn brs(&self) -> bool {
+        self.brs
+    }
+
+    pub fn set_brs(&mut self, brs: bool) {
+        self.brs = brs;
+    }
+
+    pub fn esi(&self) -> bool {
+        self.esi
+    }
+
+    pub fn set_esi(&mut self, esi: bool) {
+        self.esi = esi;
+    }
+
+    pub fn id(&self) -> ExtendedID {
+        self.id
+    }
+}
+
+impl FdDataFrame {
+    pub fn new(id: ID) -> Self {
+        match id {
+            ID::BaseID(id) => FdDataFrame::FdBaseDataFrame(FdBaseDataFrame::new(id)),
+            ID::ExtendedID(id) => FdDataFrame::FdExtendedDataFrame(FdExtendedDataFrame::new(id)),
+        }
+    }
+
+    pub fn set_data_length(&mut self, length: usize) {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref mut f) => f.set_data_length(length),
+            FdDataFrame::FdExtendedDataFrame(ref mut f) => f.set_data_length(length),
+        }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref f) => f.data(),
+            FdDataFrame::FdExtendedDataFrame(ref f) => f.data(),
+        }
+    }
+
+    pub fn data_as_mut(&mut self) -> &mut[u8] {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref mut f) => f.data_as_mut(),
+            FdDataFrame::FdExtendedDataFrame(ref mut f) => f.data_as_mut(),
+        }
+    }
+
+    pub fn brs(&self) -> bool {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref f) => f.brs(),
+            FdDataFrame::FdExtendedDataFrame(ref f) => f.brs(),
+        }
+    }
+
+    pub fn set_brs(&mut self, brs: bool) {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref mut f) => f.set_brs(brs),
+            FdDataFrame::FdExtendedDataFrame(ref mut f) => f.set_brs(brs),
+        }
+    }
+
+    pub fn esi(&self) -> bool {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref f) => f.esi(),
+            FdDataFrame::FdExtendedDataFrame(ref f) => f.esi(),
+        }
+    }
+
+    pub fn set_esi(&mut self, esi: bool) {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref mut f) => f.set_esi(esi),
+            FdDataFrame::FdExtendedDataFrame(ref mut f) => f.set_esi(esi),
+        }
+    }
+
+    pub fn id(&self) -> ID {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(f) => ID::BaseID(f.id()),
+            FdDataFrame::FdExtendedDataFrame(f) => ID::ExtendedID(f.id()),
+        }
+    }
+
+    /// Returns the raw 4-bit data-length code of this frame.
+    fn dlc(&self) -> u8 {
+        match *self {
+            FdDataFrame::FdBaseDataFrame(ref f) => f.dlc,
+            FdDataFrame::FdExtendedDataFrame(ref f) => f.dlc,
+        }
+    }
+}
+
 pub enum CanFrame {
     DataFrame(DataFrame),
     RemoteFrame(RemoteFrame),
+
+    /// A CAN FD data frame
+    FdDataFrame(FdDataFrame),
 }
 
+/// Set in the flag byte when the frame carries a 29-bit extended identifier.
+const FLAG_EXTENDED: u8 = 0b01;
+/// Set in the flag byte when the frame is a remote transmission request.
+const FLAG_REMOTE: u8 = 0b10;
+/// Set in the flag byte when the frame is a CAN FD data frame.
+const FLAG_FD: u8 = 0b100;
+/// Set in the flag byte when a CAN FD frame requests a bit-rate switch.
+const FLAG_BRS: u8 = 0b1000;
+/// Set in the flag byte when a CAN FD frame signals the error state indicator.
+const FLAG_ESI: u8 = 0b1_0000;
+
+/// Number of header bytes preceding the payload in the serialized form.
+const HEADER_LEN: usize = 6;
+
 impl CanFrame {
     pub fn id(&self) -> ID {
         match *self {
             CanFrame::DataFrame(ref f) => f.id(),
             CanFrame::RemoteFrame(ref f) => f.id(),
+            CanFrame::FdDataFrame(ref f) => f.id(),
+        }
+    }
+
+    /// Serializes this frame into `buf`, returning the number of bytes written.
+    ///
+    /// The layout is a single flag byte (extended/remote/FD, plus BRS/ESI for an FD frame), a
+    /// 4-byte big-endian identifier, the DLC byte (the 4-bit data-length code for an FD frame) and
+    /// finally the data bytes (none for a remote frame). `Error::InvalidInput` is
+    /// returned if `buf` is too small to hold the frame.
+    pub fn to_bytes(&self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut flags = 0;
+        if let ID::ExtendedID(_) = self.id() {
+            flags |= FLAG_EXTENDED;
+        }
+
+        let (dlc, data): (u8, &[u8]) = match *self {
+            CanFrame::DataFrame(ref f) => (f.data().len() as u8, f.data()),
+            CanFrame::RemoteFrame(ref f) => {
+                flags |= FLAG_REMOTE;
+                let dlc = match *f {
+                    RemoteFrame::BaseRemoteFrame(ref f) => f.dlc,
+                    RemoteFrame::ExtendedRemoteFrame(ref f) => f.dlc,
+                };
+                (dlc, &[])
+            },
+            CanFrame::FdDataFrame(ref f) => {
+                flags |= FLAG_FD;
+                if f.brs() {
+                    flags |= FLAG_BRS;
+                }
+                if f.esi() {
+                    flags |= FLAG_ESI;
+                }
+                (f.dlc(), f.data())
+            },
+        };
+
+        let length = HEADER_LEN + data.len();
+        if buf.len() < length {
+            return Err(Error::InvalidInput);
+        }
+
+        buf[0] = flags;
+        BigEndian::write_u32(&mut buf[1..5], u32::from(self.id()));
+        buf[5] = dlc;
+        buf[HEADER_LEN..length].clone_from_slice(data);
+        Ok(length)
+    }
+
+    /// Deserializes a frame from `buf` as written by `to_bytes`.
+    ///
+    /// Returns `Error::InvalidInput` if `buf` is shorter than the header or than the declared
+    /// payload length.
+    pub fn from_bytes(buf: &[u8]) -> io::Result<CanFrame> {
+        if buf.len() < HEADER_LEN {
+            return Err(Error::InvalidInput);
+        }
+
+        let flags = buf[0];
+        let raw_id = BigEndian::read_u32(&buf[1..5]);
+        let dlc = buf[5] as usize;
+
+        let id = if flags & FLAG_EXTENDED != 0 {
+            if raw_id & 0xe000_0000 != 0 {
+                return Err(Error::InvalidInput);
+            }
+            ID::ExtendedID(ExtendedID::new(raw_id))
+        } else {
+            if raw_id & 0xffff_f800 != 0 {
+                return Err(Error::InvalidInput);
+            }
+            ID::BaseID(BaseID::new(raw_id as u16))
+        };
+
+        if flags & FLAG_FD != 0 {
+            let length = fd_data_length(dlc as u8);
+            if buf.len() < HEADER_LEN + length {
+                return Err(Error::InvalidInput);
+            }
+            let mut frame = FdDataFrame::new(id);
+            frame.set_data_length(length);
+            frame.set_brs(flags & FLAG_BRS != 0);
+            frame.set_esi(flags & FLAG_ESI != 0);
+            frame.data_as_mut().clone_from_slice(&buf[HEADER_LEN..HEADER_LEN+length]);
+            Ok(CanFrame::from(frame))
+        } else if flags & FLAG_REMOTE != 0 {
+            if dlc > 8 {
+                return Err(Error::InvalidInput);
+            }
+            let mut frame = RemoteFrame::new(id);
+            frame.set_data_length(dlc);
+            Ok(CanFrame::from(frame))
+        } else {
+            if dlc > 8 || buf.len() < HEADER_LEN + dlc {
+                return Err(Error::InvalidInput);
+            }
+            let mut frame = DataFrame::new(id);
+            frame.set_data_length(dlc);
+            frame.data_as_mut().clone_from_slice(&buf[HEADER_LEN..HEADER_LEN+dlc]);
+            Ok(CanFrame::from(frame))
         }
     }
 }
@@ -299,3 +624,33 @@ impl From<ExtendedRemoteFrame> for CanFrame {
         CanFrame::from(RemoteFrame::from(f))
     }
 }
+
+impl From<FdBaseDataFrame> for FdDataFrame {
+    fn from(f: FdBaseDataFrame) -> FdDataFrame {
+        FdDataFrame::FdBaseDataFrame(f)
+    }
+}
+
+impl From<FdExtendedDataFrame> for FdDataFrame {
+    fn from(f: FdExtendedDataFrame) -> FdDataFrame {
+        FdDataFrame::FdExtendedDataFrame(f)
+    }
+}
+
+impl From<FdDataFrame> for CanFrame {
+    fn from(f: FdDataFrame) -> CanFrame {
+        CanFrame::FdDataFrame(f)
+    }
+}
+
+impl From<FdBaseDataFrame> for CanFrame {
+    fn from(f: FdBaseDataFrame) -> CanFrame {
+        CanFrame::from(FdDataFrame::from(f))
+    }
+}
+
+impl From<FdExtendedDataFrame> for CanFrame {
+    fn from(f: FdExtendedDataFrame) -> CanFrame {
+        CanFrame::from(FdDataFrame::from(f))
+    }
+}
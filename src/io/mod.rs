@@ -10,14 +10,15 @@ pub fn blocking<F, O, E>(non_blocking: F) -> result::Result<O, E>
     loop {
         match non_blocking() {
             Err(x) => {
-                if x.clone().into() != Error::BufferExhausted {
-                    return Err(x);
+                match x.clone().into() {
+                    Error::WouldBlock | Error::Interrupted => (),
+                    _ => return Err(x),
                 }
             },
             Ok(x) => {
                 return Ok(x);
             },
-        }            
+        }
     }
 }
 
@@ -27,18 +28,83 @@ pub type Result<T> = result::Result<T, Error>;
 /// Common transmit/receive errors.
 /// This list is intended to grow over time and it is not recommended to exhaustively match against it.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
-    
-    /// In case of transmissions: Buffer full. In case of reception: Buffer empty.
-    BufferExhausted,
+
+    /// The operation is not ready and should be retried later.
+    ///
+    /// In case of transmissions: buffer full. In case of reception: buffer empty.
+    WouldBlock,
+
+    /// A retried operation was interrupted before it could complete and should be retried.
+    Interrupted,
+
     InvalidInput,
 
     /// A reception can fail with this error if it's grounded in the parity checking, CRC calculation or similar.
     ErrorDetectionCode,
-    
+
+    Other,
+}
+
+/// A coarse categorization of an [`Error`], mirroring the `embedded-io` error taxonomy.
+///
+/// This lets downstream code match on the kind of failure without depending on the exact shape of
+/// the (non-exhaustive) `Error` enum.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// See [`Error::WouldBlock`].
+    WouldBlock,
+    /// See [`Error::Interrupted`].
+    Interrupted,
+    /// See [`Error::InvalidInput`].
+    InvalidInput,
+    /// See [`Error::ErrorDetectionCode`].
+    ErrorDetectionCode,
+    /// Any other error.
     Other,
 }
 
+impl Error {
+    /// Returns the coarse [`ErrorKind`] this error belongs to.
+    pub fn kind(&self) -> ErrorKind {
+        match *self {
+            Error::WouldBlock => ErrorKind::WouldBlock,
+            Error::Interrupted => ErrorKind::Interrupted,
+            Error::InvalidInput => ErrorKind::InvalidInput,
+            Error::ErrorDetectionCode => ErrorKind::ErrorDetectionCode,
+            Error::Other => ErrorKind::Other,
+        }
+    }
+
+    /// Returns `true` if retrying the operation would currently block.
+    pub fn is_would_block(&self) -> bool {
+        *self == Error::WouldBlock
+    }
+
+    /// Returns `true` if a retried operation was interrupted and should be retried again.
+    pub fn is_interrupted(&self) -> bool {
+        *self == Error::Interrupted
+    }
+}
+
+/// A trait for readers that can report whether a read would currently make progress.
+///
+/// Intended to be polled before issuing a non-blocking `read_until`.
+pub trait ReadReady {
+    /// Returns `true` if data is available to read without returning `Error::WouldBlock`.
+    fn read_ready(&mut self) -> Result<bool>;
+}
+
+/// A trait for writers that can report whether a write would currently make progress.
+///
+/// Intended to be polled before issuing a non-blocking `write`.
+pub trait WriteReady {
+    /// Returns `true` if the writer can accept data without returning `Error::WouldBlock`.
+    fn write_ready(&mut self) -> Result<bool>;
+}
+
 /// A trait for objects which are byte-oriented sinks.
 ///
 /// This is very similar to the standard library's `io::Write` and share similiarities with `fmt::Write`.
@@ -51,29 +117,29 @@ pub trait Write {
 
     /// Attempts to write an entire buffer into this write.
     ///
-    /// This method will continously call write untill there is no more data or an error of non `Error::BufferExhausted` kind is returned.
+    /// This method will continously call write untill there is no more data or an error of non `Error::WouldBlock`/`Error::Interrupted` kind is returned.
     fn write_all(&mut self, buf: &[u8]) -> Result<()> {
         let mut bytes_written = 0;
         while bytes_written < buf.len() {
             match self.write(&buf[bytes_written..]) {
                 Ok(n) => bytes_written += n,
-                Err(Error::BufferExhausted) => (),
+                Err(Error::WouldBlock) | Err(Error::Interrupted) => (),
                 Err(e) => return Err(e),
             }
         }
         Ok(())
     }
-    
+
     /// Attempts to write a str into this write.
     ///
-    /// This method will continously call write untill there is no more data or an error of non `Error::BufferExhausted` kind is returned.
+    /// This method will continously call write untill there is no more data or an error of non `Error::WouldBlock`/`Error::Interrupted` kind is returned.
     fn write_str(&mut self, s: &str) -> Result<()> {
         self.write_all(s.as_bytes())
     }
 
     /// Writes a formatted string into this writer, returning any error encountered.
     ///
-    /// This method will continously call write untill there is no more data or an error of non `Error::BufferExhausted` kind is returned.
+    /// This method will continously call write untill there is no more data or an error of non `Error::WouldBlock`/`Error::Interrupted` kind is returned.
     #[allow(unused_must_use)]
     fn write_fmt(&mut self, args: fmt::Arguments) -> Result<()> {
         // This Adapter is needed to allow `self` (of type `&mut
@@ -133,11 +199,442 @@ pub trait Read {
     fn read_until(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize>;
 }
 
+/// A buffered wrapper around a `Write`.
+///
+/// `BufWriter` accumulates bytes into a fixed-size internal buffer and only calls the
+/// underlying `write` once the buffer is full or `flush` is called explicitly. This is a win
+/// on embedded transports where every call to the underlying `write` maps to a relatively
+/// expensive device transaction (a UART register poke, an SPI transfer, ...).
+///
+/// The buffer capacity is a const generic so that no allocation is required in `no_std`.
+/// Any data still held in the buffer is flushed when the `BufWriter` is dropped.
+pub struct BufWriter<W: Write, const N: usize> {
+    inner: W,
+    buffer: [u8; N],
+    index: usize,
+}
+
+impl<W: Write, const N: usize> BufWriter<W, N> {
+    /// Creates a new `BufWriter` with an empty buffer wrapping `inner`.
+    pub fn new(inner: W) -> Self {
+        BufWriter{inner: inner, buffer: [0u8; N], index: 0}
+    }
+
+    /// Writes out any bytes currently held in the buffer to the underlying writer.
+    ///
+    /// As with `write_all`, an `Error::WouldBlock`/`Error::Interrupted` from the inner writer is
+    /// treated as a request to retry rather than a failure.
+    pub fn flush(&mut self) -> Result<()> {
+        if self.index > 0 {
+            self.inner.write_all(&self.buffer[..self.index])?;
+            self.index = 0;
+        }
+        Ok(())
+    }
+
+    /// Gets a shared reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing directly to the inner writer while the buffer is non-empty will desequence the
+    /// output; call `flush` first if that matters.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Flushes the buffer and unwraps this `BufWriter`, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        let mut this = core::mem::ManuallyDrop::new(self);
+        let _ = this.flush();
+        unsafe { core::ptr::read(&this.inner) }
+    }
+}
+
+impl<W: Write, const N: usize> Write for BufWriter<W, N> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = core::cmp::min(N - self.index, buf.len());
+        self.buffer[self.index..self.index+n].clone_from_slice(&buf[..n]);
+        self.index += n;
+        if self.index == N {
+            self.flush()?;
+        }
+        Ok(n)
+    }
+}
+
+impl<W: Write, const N: usize> Drop for BufWriter<W, N> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// A buffered wrapper around a `Read`.
+///
+/// `BufReader` pulls bytes from the underlying reader in large chunks into a fixed-size internal
+/// buffer and serves `read_until` (and the `fill_buf`/`consume` pair) from it, only touching the
+/// underlying reader again once the buffer has been emptied. The capacity is a const generic so
+/// no allocation is required in `no_std`.
+pub struct BufReader<R: Read, const N: usize> {
+    inner: R,
+    buffer: [u8; N],
+    /// Index of the next unconsumed byte in `buffer`.
+    index: usize,
+    /// Number of valid bytes currently held in `buffer`.
+    length: usize,
+}
+
+impl<R: Read, const N: usize> BufReader<R, N> {
+    /// Creates a new `BufReader` with an empty buffer wrapping `inner`.
+    pub fn new(inner: R) -> Self {
+        BufReader{inner: inner, buffer: [0u8; N], index: 0, length: 0}
+    }
+
+    /// Returns the buffered bytes, refilling from the underlying reader if the buffer is empty.
+    ///
+    /// A refill is a single `read_until` against the underlying reader; because no delimiter is
+    /// implied here the fill may stop early on a `0` byte, which only affects chunk size, never
+    /// correctness.
+    pub fn fill_buf(&mut self) -> Result<&[u8]> {
+        if self.index == self.length {
+            self.length = self.inner.read_until(0, &mut self.buffer)?;
+            self.index = 0;
+        }
+        Ok(&self.buffer[self.index..self.length])
+    }
+
+    /// Marks `amount` bytes from the buffer as consumed, so they are not returned again.
+    pub fn consume(&mut self, amount: usize) {
+        self.index = core::cmp::min(self.index + amount, self.length);
+    }
+
+    /// Gets a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Unwraps this `BufReader`, returning the underlying reader and discarding any buffered data.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read, const N: usize> Read for BufReader<R, N> {
+    fn read_until(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+        if self.index == self.length {
+            self.length = self.inner.read_until(byte, &mut self.buffer)?;
+            self.index = 0;
+        }
+        let mut written = 0;
+        while self.index < self.length && written < buf.len() {
+            let b = self.buffer[self.index];
+            buf[written] = b;
+            self.index += 1;
+            written += 1;
+            if b == byte {
+                break;
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// A `Read`/`Write` implementor backed by an in-memory byte buffer.
+///
+/// `Cursor` wraps anything that can be viewed as a byte slice and tracks a read/write position
+/// into it, much like the standard library's `io::Cursor`. It gives a zero-dependency way to
+/// serialize frames into a `[u8; N]` and to unit-test `Read`/`Write` implementors without a real
+/// peripheral.
+pub struct Cursor<T> {
+    inner: T,
+    position: usize,
+}
+
+impl<T> Cursor<T> {
+    /// Creates a new cursor wrapping `inner`, positioned at the start.
+    pub fn new(inner: T) -> Self {
+        Cursor{inner: inner, position: 0}
+    }
+
+    /// Returns the current position of this cursor.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Sets the position of this cursor.
+    pub fn set_position(&mut self, position: usize) {
+        self.position = position;
+    }
+
+    /// Unwraps this cursor, returning the underlying buffer.
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+}
+
+impl<T: AsRef<[u8]>> Read for Cursor<T> {
+    fn read_until(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+        let inner = self.inner.as_ref();
+        let mut written = 0;
+        while self.position < inner.len() && written < buf.len() {
+            let b = inner[self.position];
+            buf[written] = b;
+            self.position += 1;
+            written += 1;
+            if b == byte {
+                break;
+            }
+        }
+        Ok(written)
+    }
+}
+
+impl<T: AsMut<[u8]>> Write for Cursor<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let inner = self.inner.as_mut();
+        if self.position >= inner.len() && !buf.is_empty() {
+            // The backing slice has a fixed size and never drains, so this overflow is
+            // permanent; return a non-retryable error rather than `WouldBlock` so that
+            // `write_all`/`write_frame` surface it instead of spinning forever.
+            return Err(Error::Other);
+        }
+        let n = core::cmp::min(inner.len() - self.position, buf.len());
+        inner[self.position..self.position+n].clone_from_slice(&buf[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+/// Describes how multi-byte integers are laid out in a byte buffer.
+///
+/// The two implementors, `LittleEndian` and `BigEndian`, are zero-sized marker types used to
+/// parameterize the `ReadBytesExt`/`WriteBytesExt` extension traits.
+pub trait ByteOrder {
+    /// Reads a `u16` from the first two bytes of `buf`.
+    fn read_u16(buf: &[u8]) -> u16;
+    /// Reads a `u32` from the first four bytes of `buf`.
+    fn read_u32(buf: &[u8]) -> u32;
+    /// Reads an `i16` from the first two bytes of `buf`.
+    fn read_i16(buf: &[u8]) -> i16 {
+        Self::read_u16(buf) as i16
+    }
+    /// Reads an `i32` from the first four bytes of `buf`.
+    fn read_i32(buf: &[u8]) -> i32 {
+        Self::read_u32(buf) as i32
+    }
+    /// Writes `n` into the first two bytes of `buf`.
+    fn write_u16(buf: &mut [u8], n: u16);
+    /// Writes `n` into the first four bytes of `buf`.
+    fn write_u32(buf: &mut [u8], n: u32);
+    /// Writes `n` into the first two bytes of `buf`.
+    fn write_i16(buf: &mut [u8], n: i16) {
+        Self::write_u16(buf, n as u16)
+    }
+    /// Writes `n` into the first four bytes of `buf`.
+    fn write_i32(buf: &mut [u8], n: i32) {
+        Self::write_u32(buf, n as u32)
+    }
+}
+
+/// Little-endian byte order, least significant byte first.
+pub struct LittleEndian;
+
+/// Big-endian (network) byte order, most significant byte first.
+pub struct BigEndian;
+
+impl ByteOrder for LittleEndian {
+    fn read_u16(buf: &[u8]) -> u16 {
+        (buf[0] as u16) | (buf[1] as u16) << 8
+    }
+    fn read_u32(buf: &[u8]) -> u32 {
+        (buf[0] as u32) | (buf[1] as u32) << 8 | (buf[2] as u32) << 16 | (buf[3] as u32) << 24
+    }
+    fn write_u16(buf: &mut [u8], n: u16) {
+        buf[0] = n as u8;
+        buf[1] = (n >> 8) as u8;
+    }
+    fn write_u32(buf: &mut [u8], n: u32) {
+        buf[0] = n as u8;
+        buf[1] = (n >> 8) as u8;
+        buf[2] = (n >> 16) as u8;
+        buf[3] = (n >> 24) as u8;
+    }
+}
+
+impl ByteOrder for BigEndian {
+    fn read_u16(buf: &[u8]) -> u16 {
+        (buf[1] as u16) | (buf[0] as u16) << 8
+    }
+    fn read_u32(buf: &[u8]) -> u32 {
+        (buf[3] as u32) | (buf[2] as u32) << 8 | (buf[1] as u32) << 16 | (buf[0] as u32) << 24
+    }
+    fn write_u16(buf: &mut [u8], n: u16) {
+        buf[0] = (n >> 8) as u8;
+        buf[1] = n as u8;
+    }
+    fn write_u32(buf: &mut [u8], n: u32) {
+        buf[0] = (n >> 24) as u8;
+        buf[1] = (n >> 16) as u8;
+        buf[2] = (n >> 8) as u8;
+        buf[3] = n as u8;
+    }
+}
+
+/// Extends `Read` with methods for reading typed integers with a defined byte order.
+///
+/// This is a blanket-implemented extension trait, so it is available on every `Read` implementor
+/// (including `BufReader` and `Cursor`) without any further work.
+pub trait ReadBytesExt: Read {
+    /// Reads exactly `buf.len()` bytes, looping over `read_until` until the buffer is full.
+    ///
+    /// Returns `Error::WouldBlock` only if the source ends before enough bytes are available.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.read_until(0, &mut buf[filled..])? {
+                0 => return Err(Error::WouldBlock),
+                n => filled += n,
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads a `u16` in the byte order `T`.
+    fn read_u16<T: ByteOrder>(&mut self) -> Result<u16> {
+        let mut buf = [0u8; 2];
+        self.read_exact(&mut buf)?;
+        Ok(T::read_u16(&buf))
+    }
+
+    /// Reads a `u32` in the byte order `T`.
+    fn read_u32<T: ByteOrder>(&mut self) -> Result<u32> {
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)?;
+        Ok(T::read_u32(&buf))
+    }
+
+    /// Reads an `i16` in the byte order `T`.
+    fn read_i16<T: ByteOrder>(&mut self) -> Result<i16> {
+        let mut buf = [0u8; 2];
+        self.read_exact(&mut buf)?;
+        Ok(T::read_i16(&buf))
+    }
+
+    /// Reads an `i32` in the byte order `T`.
+    fn read_i32<T: ByteOrder>(&mut self) -> Result<i32> {
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)?;
+        Ok(T::read_i32(&buf))
+    }
+
+    /// Reads a little-endian `u16`.
+    fn read_u16_le(&mut self) -> Result<u16> { self.read_u16::<LittleEndian>() }
+    /// Reads a big-endian `u16`.
+    fn read_u16_be(&mut self) -> Result<u16> { self.read_u16::<BigEndian>() }
+    /// Reads a little-endian `u32`.
+    fn read_u32_le(&mut self) -> Result<u32> { self.read_u32::<LittleEndian>() }
+    /// Reads a big-endian `u32`.
+    fn read_u32_be(&mut self) -> Result<u32> { self.read_u32::<BigEndian>() }
+    /// Reads a little-endian `i16`.
+    fn read_i16_le(&mut self) -> Result<i16> { self.read_i16::<LittleEndian>() }
+    /// Reads a big-endian `i16`.
+    fn read_i16_be(&mut self) -> Result<i16> { self.read_i16::<BigEndian>() }
+    /// Reads a little-endian `i32`.
+    fn read_i32_le(&mut self) -> Result<i32> { self.read_i32::<LittleEndian>() }
+    /// Reads a big-endian `i32`.
+    fn read_i32_be(&mut self) -> Result<i32> { self.read_i32::<BigEndian>() }
+}
+
+impl<R: Read + ?Sized> ReadBytesExt for R {}
+
+/// Extends `Write` with methods for writing typed integers with a defined byte order.
+///
+/// Like `ReadBytesExt` this is blanket-implemented for every `Write` implementor.
+pub trait WriteBytesExt: Write {
+    /// Writes a `u16` in the byte order `T`.
+    fn write_u16<T: ByteOrder>(&mut self, n: u16) -> Result<()> {
+        let mut buf = [0u8; 2];
+        T::write_u16(&mut buf, n);
+        self.write_all(&buf)
+    }
+
+    /// Writes a `u32` in the byte order `T`.
+    fn write_u32<T: ByteOrder>(&mut self, n: u32) -> Result<()> {
+        let mut buf = [0u8; 4];
+        T::write_u32(&mut buf, n);
+        self.write_all(&buf)
+    }
+
+    /// Writes an `i16` in the byte order `T`.
+    fn write_i16<T: ByteOrder>(&mut self, n: i16) -> Result<()> {
+        let mut buf = [0u8; 2];
+        T::write_i16(&mut buf, n);
+        self.write_all(&buf)
+    }
+
+    /// Writes an `i32` in the byte order `T`.
+    fn write_i32<T: ByteOrder>(&mut self, n: i32) -> Result<()> {
+        let mut buf = [0u8; 4];
+        T::write_i32(&mut buf, n);
+        self.write_all(&buf)
+    }
+
+    /// Writes a little-endian `u16`.
+    fn write_u16_le(&mut self, n: u16) -> Result<()> { self.write_u16::<LittleEndian>(n) }
+    /// Writes a big-endian `u16`.
+    fn write_u16_be(&mut self, n: u16) -> Result<()> { self.write_u16::<BigEndian>(n) }
+    /// Writes a little-endian `u32`.
+    fn write_u32_le(&mut self, n: u32) -> Result<()> { self.write_u32::<LittleEndian>(n) }
+    /// Writes a big-endian `u32`.
+    fn write_u32_be(&mut self, n: u32) -> Result<()> { self.write_u32::<BigEndian>(n) }
+    /// Writes a little-endian `i16`.
+    fn write_i16_le(&mut self, n: i16) -> Result<()> { self.write_i16::<LittleEndian>(n) }
+    /// Writes a big-endian `i16`.
+    fn write_i16_be(&mut self, n: i16) -> Result<()> { self.write_i16::<BigEndian>(n) }
+    /// Writes a little-endian `i32`.
+    fn write_i32_le(&mut self, n: i32) -> Result<()> { self.write_i32::<LittleEndian>(n) }
+    /// Writes a big-endian `i32`.
+    fn write_i32_be(&mut self, n: i32) -> Result<()> { self.write_i32::<BigEndian>(n) }
+}
+
+impl<W: Write + ?Sized> WriteBytesExt for W {}
+
+/// Writes `payload` to `writer` as a length-prefixed frame.
+///
+/// The frame is a fixed 4-byte big-endian length header followed by the payload bytes, letting a
+/// stream-oriented transport delimit individual messages. The whole payload is written with
+/// `write_all`.
+pub fn write_frame<W: Write>(writer: &mut W, payload: &[u8]) -> Result<()> {
+    writer.write_u32_be(payload.len() as u32)?;
+    writer.write_all(payload)
+}
+
+/// Reads a length-prefixed frame from `reader` into `buf`, returning the payload length.
+///
+/// The 4-byte big-endian length header is read first and validated against the capacity of `buf`;
+/// `Error::InvalidInput` is returned if the declared length would overflow the supplied buffer.
+/// Exactly that many payload bytes are then read into the start of `buf`.
+pub fn read_frame<R: Read>(reader: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let length = reader.read_u32_be()? as usize;
+    if length > buf.len() {
+        return Err(Error::InvalidInput);
+    }
+    reader.read_exact(&mut buf[..length])?;
+    Ok(length)
+}
+
 #[cfg(test)]
 mod tests {
 
     use io::*;
-    
+
     #[test]
     fn write_test() {
         struct TestBuffer {
@@ -160,4 +657,193 @@ mod tests {
         assert_eq!(test_buffer.buffer[..test_buffer.index].len(), "This is a test".as_bytes().len());
         assert_eq!(&test_buffer.buffer[..test_buffer.index], "This is a test".as_bytes());
     }
+
+    struct CountingWriter {
+        buffer: [u8; 100],
+        index: usize,
+        writes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> Result<usize> {
+            self.buffer[self.index..self.index+buf.len()].clone_from_slice(buf);
+            self.index += buf.len();
+            self.writes += 1;
+            Ok(buf.len())
+        }
+    }
+
+    #[test]
+    fn buf_writer_batches_writes() {
+        let mut writer: BufWriter<CountingWriter, 4> =
+            BufWriter::new(CountingWriter{buffer: [0u8; 100], index: 0, writes: 0});
+
+        // Two bytes fit in the buffer and do not reach the inner writer yet.
+        assert_eq!(writer.write(&[1, 2]).unwrap(), 2);
+        assert_eq!(writer.get_ref().writes, 0);
+
+        // Filling and overflowing the buffer forces a single inner write of the full buffer.
+        assert_eq!(writer.write(&[3, 4, 5]).unwrap(), 2);
+        assert_eq!(writer.get_ref().writes, 1);
+
+        // The byte that did not fit is buffered and flushed by `into_inner`.
+        assert_eq!(writer.write(&[5]).unwrap(), 1);
+
+        let inner = writer.into_inner();
+        assert_eq!(inner.writes, 2);
+        assert_eq!(&inner.buffer[..inner.index], &[1, 2, 3, 4, 5]);
+    }
+
+    struct SliceReader {
+        data: [u8; 8],
+        index: usize,
+        length: usize,
+    }
+
+    impl Read for SliceReader {
+        fn read_until(&mut self, byte: u8, buf: &mut [u8]) -> Result<usize> {
+            let mut written = 0;
+            while self.index < self.length && written < buf.len() {
+                let b = self.data[self.index];
+                buf[written] = b;
+                self.index += 1;
+                written += 1;
+                if b == byte {
+                    break;
+                }
+            }
+            Ok(written)
+        }
+    }
+
+    #[test]
+    fn buf_reader_serves_from_buffer() {
+        let mut reader: BufReader<SliceReader, 8> =
+            BufReader::new(SliceReader{data: [b'a', b'b', b'\n', b'c', b'd', 0, 0, 0], index: 0, length: 5});
+
+        let mut line = [0u8; 8];
+        let n = reader.read_until(b'\n', &mut line).unwrap();
+        assert_eq!(&line[..n], b"ab\n");
+
+        let n = reader.read_until(b'\n', &mut line).unwrap();
+        assert_eq!(&line[..n], b"cd");
+    }
+
+    #[test]
+    fn cursor_write_then_read() {
+        let mut cursor = Cursor::new([0u8; 4]);
+        assert_eq!(cursor.write(&[1, 2, 3]).unwrap(), 3);
+        assert_eq!(cursor.position(), 3);
+
+        // Backing slice only has room for one more byte.
+        assert_eq!(cursor.write(&[4, 5]).unwrap(), 1);
+        assert_eq!(cursor.write(&[6]), Err(Error::Other));
+
+        let mut cursor = Cursor::new(cursor.into_inner());
+        let mut buf = [0u8; 4];
+        let n = cursor.read_until(3, &mut buf).unwrap();
+        assert_eq!(&buf[..n], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn byteorder_roundtrip() {
+        let mut cursor = Cursor::new([0u8; 6]);
+        cursor.write_u16_be(0x0102).unwrap();
+        cursor.write_u32_le(0x0a0b0c0d).unwrap();
+        assert_eq!(cursor.into_inner(), [0x01, 0x02, 0x0d, 0x0c, 0x0b, 0x0a]);
+
+        let mut cursor = Cursor::new([0x01u8, 0x02, 0x0d, 0x0c, 0x0b, 0x0a]);
+        assert_eq!(cursor.read_u16_be().unwrap(), 0x0102);
+        assert_eq!(cursor.read_u32_le().unwrap(), 0x0a0b0c0d);
+    }
+
+    #[test]
+    fn byteorder_short_source_errors() {
+        let mut cursor = Cursor::new([0x01u8]);
+        assert_eq!(cursor.read_u16_be(), Err(Error::WouldBlock));
+    }
+
+    #[test]
+    fn error_kind_accessor() {
+        assert_eq!(Error::WouldBlock.kind(), ErrorKind::WouldBlock);
+        assert!(Error::WouldBlock.is_would_block());
+        assert!(Error::Interrupted.is_interrupted());
+        assert!(!Error::Other.is_would_block());
+    }
+
+    #[test]
+    fn frame_roundtrip() {
+        let mut storage = [0u8; 16];
+        {
+            let mut cursor = Cursor::new(&mut storage[..]);
+            write_frame(&mut cursor, &[0xde, 0xad, 0xbe, 0xef]).unwrap();
+        }
+
+        let mut cursor = Cursor::new(&storage[..]);
+        let mut payload = [0u8; 8];
+        let n = read_frame(&mut cursor, &mut payload).unwrap();
+        assert_eq!(&payload[..n], &[0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn frame_rejects_oversized_payload() {
+        let mut storage = [0u8; 16];
+        {
+            let mut cursor = Cursor::new(&mut storage[..]);
+            write_frame(&mut cursor, &[1, 2, 3, 4, 5, 6]).unwrap();
+        }
+        let mut cursor = Cursor::new(&storage[..]);
+        let mut small = [0u8; 4];
+        assert_eq!(read_frame(&mut cursor, &mut small), Err(Error::InvalidInput));
+    }
+
+    #[test]
+    fn canframe_roundtrip() {
+        use can::*;
+
+        let mut frame = DataFrame::new(ID::ExtendedID(ExtendedID::new(0x1234)));
+        frame.set_data_length(3);
+        frame.data_as_mut().clone_from_slice(&[0xaa, 0xbb, 0xcc]);
+        let frame = CanFrame::from(frame);
+
+        let mut buf = [0u8; 16];
+        let n = frame.to_bytes(&mut buf).unwrap();
+
+        match CanFrame::from_bytes(&buf[..n]).unwrap() {
+            CanFrame::DataFrame(f) => {
+                assert_eq!(u32::from(f.id()), 0x1234);
+                assert_eq!(f.data(), &[0xaa, 0xbb, 0xcc]);
+            },
+            _ => panic!("expected a data frame"),
+        }
+    }
+
+    #[test]
+    fn fd_canframe_roundtrip() {
+        use can::*;
+
+        let mut frame = FdDataFrame::new(ID::BaseID(BaseID::new(0x123)));
+        // 10 bytes is not a valid FD size; it rounds up to the 12-byte payload.
+        frame.set_data_length(10);
+        frame.set_brs(true);
+        assert_eq!(frame.data().len(), 12);
+        for (i, b) in frame.data_as_mut().iter_mut().enumerate() {
+            *b = i as u8;
+        }
+        let frame = CanFrame::from(frame);
+
+        let mut buf = [0u8; 32];
+        let n = frame.to_bytes(&mut buf).unwrap();
+
+        match CanFrame::from_bytes(&buf[..n]).unwrap() {
+            CanFrame::FdDataFrame(f) => {
+                assert_eq!(u32::from(f.id()), 0x123);
+                assert_eq!(f.data().len(), 12);
+                assert!(f.brs());
+                assert!(!f.esi());
+                assert_eq!(f.data()[11], 11);
+            },
+            _ => panic!("expected an FD data frame"),
+        }
+    }
 }